@@ -0,0 +1,47 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum SignalProtocolError {
+    InvalidSenderKeyId,
+    SenderKeySigningKeyMissing,
+    DuplicatedMessage(u32, u32),
+    InvalidMessage(&'static str),
+    SignatureValidationFailed,
+    SenderKeyShareVerificationFailed,
+    SenderKeyDistributionNotAuthorized,
+    SenderKeySeedVrfInvalid,
+}
+
+impl fmt::Display for SignalProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignalProtocolError::InvalidSenderKeyId => write!(f, "invalid sender key id"),
+            SignalProtocolError::SenderKeySigningKeyMissing => {
+                write!(f, "sender key record does not have a signing key")
+            }
+            SignalProtocolError::DuplicatedMessage(current, received) => write!(
+                f,
+                "received message with old counter: {} , {}",
+                current, received
+            ),
+            SignalProtocolError::InvalidMessage(s) => write!(f, "invalid message: {}", s),
+            SignalProtocolError::SignatureValidationFailed => {
+                write!(f, "signature validation failed")
+            }
+            SignalProtocolError::SenderKeyShareVerificationFailed => {
+                write!(f, "sender key DKG share failed verification")
+            }
+            SignalProtocolError::SenderKeyDistributionNotAuthorized => write!(
+                f,
+                "sender key distribution message lacks a valid admin quorum signature"
+            ),
+            SignalProtocolError::SenderKeySeedVrfInvalid => {
+                write!(f, "sender key VRF seed failed verification")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SignalProtocolError {}
+
+pub type Result<T> = std::result::Result<T, SignalProtocolError>;
@@ -1,11 +1,17 @@
 use crate::consts;
 use crate::crypto;
 use crate::curve;
+use crate::curve::PublicKey;
 use crate::error::Result;
 use crate::protocol::{SenderKeyDistributionMessage, SenderKeyMessage};
 use crate::sender_keys::{SenderKeyRecord, SenderKeyState, SenderMessageKey};
 use crate::{SenderKeyName, SenderKeyStore, SignalProtocolError};
 
+use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{Identity, VartimeMultiscalarMul};
 use rand::{CryptoRng, Rng};
 use std::convert::TryFrom;
 
@@ -45,6 +51,408 @@ pub fn group_encrypt<R: Rng + CryptoRng>(
     Ok(skm.serialized().to_vec())
 }
 
+/// Version byte for [`SenderKeyMerkleBatchMessage`]'s wire encoding. Chosen
+/// distinct from `SenderKeyMessage`'s own version nibble so a batched and a
+/// plain single-message `SenderKeyMessage` can never be confused for one
+/// another when parsed.
+const MERKLE_BATCH_MESSAGE_VERSION: u8 = 0x40;
+
+/// One leaf hash in a batch's Merkle tree: `H(key_id_i || ciphertext_i ||
+/// iteration_i)`. `key_id` is included so the root signature authenticates
+/// which sender-key epoch each message belongs to — otherwise an on-path
+/// attacker could rewrite a message's `key_id` to point at a different epoch
+/// without invalidating the root.
+fn merkle_leaf_hash(key_id: u32, ciphertext: &[u8], iteration: u32) -> Result<[u8; 32]> {
+    let mut input = Vec::with_capacity(4 + ciphertext.len() + 4);
+    input.extend_from_slice(&key_id.to_be_bytes());
+    input.extend_from_slice(ciphertext);
+    input.extend_from_slice(&iteration.to_be_bytes());
+    crypto::sha256(&input)
+}
+
+/// Combine two sibling hashes into their parent, in left-then-right order.
+fn merkle_parent_hash(left: &[u8; 32], right: &[u8; 32]) -> Result<[u8; 32]> {
+    let mut input = Vec::with_capacity(64);
+    input.extend_from_slice(left);
+    input.extend_from_slice(right);
+    crypto::sha256(&input)
+}
+
+/// Build a binary Merkle tree over `leaves` (padding by duplicating the last
+/// leaf when the level is odd), returning the root together with each leaf's
+/// authentication path (sibling hashes from the leaf up to the root).
+fn merkle_tree(leaves: &[[u8; 32]]) -> Result<([u8; 32], Vec<Vec<[u8; 32]>>)> {
+    assert!(!leaves.is_empty());
+
+    let mut paths = vec![Vec::new(); leaves.len()];
+    let mut level: Vec<[u8; 32]> = leaves.to_vec();
+    // `index_of_leaf[j]` is the position of leaf `j` within the current level.
+    let mut index_of_leaf: Vec<usize> = (0..leaves.len()).collect();
+
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(*level.last().unwrap());
+        }
+
+        for (j, idx) in index_of_leaf.iter().enumerate() {
+            let sibling = idx ^ 1;
+            paths[j].push(level[sibling]);
+        }
+
+        let mut next_level = Vec::with_capacity(level.len() / 2);
+        for pair in level.chunks_exact(2) {
+            next_level.push(merkle_parent_hash(&pair[0], &pair[1])?);
+        }
+
+        for idx in index_of_leaf.iter_mut() {
+            *idx /= 2;
+        }
+        level = next_level;
+    }
+
+    Ok((level[0], paths))
+}
+
+/// Recompute a Merkle root from a leaf hash and its authentication path,
+/// using `leaf_index`'s bits (lowest first) to decide sibling order at each
+/// level, matching the left/right convention used by [`merkle_tree`].
+fn merkle_root_from_path(
+    mut leaf_hash: [u8; 32],
+    leaf_index: u32,
+    path: &[[u8; 32]],
+) -> Result<[u8; 32]> {
+    let mut index = leaf_index;
+    for sibling in path {
+        leaf_hash = if index % 2 == 0 {
+            merkle_parent_hash(&leaf_hash, sibling)?
+        } else {
+            merkle_parent_hash(sibling, &leaf_hash)?
+        };
+        index /= 2;
+    }
+    Ok(leaf_hash)
+}
+
+/// One message out of a [`group_encrypt_batch`] call. Encrypted under its own
+/// ratcheted message key exactly like a plain `SenderKeyMessage`, but instead
+/// of carrying its own signature it carries its position in the batch's
+/// Merkle tree plus the single signature that covers the whole tree's root,
+/// so `N` outbound messages cost one signature instead of `N`.
+pub struct SenderKeyMerkleBatchMessage {
+    key_id: u32,
+    iteration: u32,
+    ciphertext: Vec<u8>,
+    leaf_index: u32,
+    auth_path: Vec<[u8; 32]>,
+    root_signature: [u8; 64],
+}
+
+impl SenderKeyMerkleBatchMessage {
+    pub fn key_id(&self) -> u32 {
+        self.key_id
+    }
+
+    pub fn iteration(&self) -> u32 {
+        self.iteration
+    }
+
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.ciphertext
+    }
+
+    /// Recompute this message's claimed Merkle root from its leaf hash and
+    /// authentication path.
+    fn claimed_root(&self) -> Result<[u8; 32]> {
+        let leaf_hash = merkle_leaf_hash(self.key_id, &self.ciphertext, self.iteration)?;
+        merkle_root_from_path(leaf_hash, self.leaf_index, &self.auth_path)
+    }
+
+    pub fn serialized(&self) -> Result<Vec<u8>> {
+        let mut out = vec![MERKLE_BATCH_MESSAGE_VERSION];
+        out.extend_from_slice(&self.key_id.to_be_bytes());
+        out.extend_from_slice(&self.iteration.to_be_bytes());
+        out.extend_from_slice(&self.leaf_index.to_be_bytes());
+        out.push(self.auth_path.len() as u8);
+        for sibling in &self.auth_path {
+            out.extend_from_slice(sibling);
+        }
+        out.extend_from_slice(&self.root_signature);
+        out.extend_from_slice(&(self.ciphertext.len() as u32).to_be_bytes());
+        out.extend_from_slice(&self.ciphertext);
+        Ok(out)
+    }
+}
+
+impl TryFrom<&[u8]> for SenderKeyMerkleBatchMessage {
+    type Error = SignalProtocolError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        const TRUNCATED: SignalProtocolError = SignalProtocolError::InvalidMessage("truncated message");
+
+        if bytes.first() != Some(&MERKLE_BATCH_MESSAGE_VERSION) {
+            return Err(SignalProtocolError::InvalidMessage(
+                "not a Merkle-batched SenderKeyMessage",
+            ));
+        }
+
+        // All reads below go through `get(..)`/checked arithmetic rather than
+        // direct slicing: every length here (`path_len`, `ciphertext_len`) is
+        // attacker-controlled, and a raw `bytes[a..b]` slice panics instead of
+        // erroring on truncated or malicious input.
+        let mut offset = 1usize;
+        let read_u32 = |bytes: &[u8], offset: &mut usize| -> Result<u32> {
+            let end = offset.checked_add(4).ok_or(TRUNCATED)?;
+            let slice = bytes.get(*offset..end).ok_or(TRUNCATED)?;
+            let value = u32::from_be_bytes(slice.try_into().map_err(|_| TRUNCATED)?);
+            *offset = end;
+            Ok(value)
+        };
+
+        let key_id = read_u32(bytes, &mut offset)?;
+        let iteration = read_u32(bytes, &mut offset)?;
+        let leaf_index = read_u32(bytes, &mut offset)?;
+
+        let path_len = *bytes.get(offset).ok_or(TRUNCATED)? as usize;
+        offset += 1;
+
+        let mut auth_path = Vec::with_capacity(path_len.min(bytes.len()));
+        for _ in 0..path_len {
+            let end = offset.checked_add(32).ok_or(TRUNCATED)?;
+            let sibling: [u8; 32] = bytes
+                .get(offset..end)
+                .ok_or(TRUNCATED)?
+                .try_into()
+                .map_err(|_| TRUNCATED)?;
+            auth_path.push(sibling);
+            offset = end;
+        }
+
+        let end = offset.checked_add(64).ok_or(TRUNCATED)?;
+        let root_signature: [u8; 64] = bytes
+            .get(offset..end)
+            .ok_or(TRUNCATED)?
+            .try_into()
+            .map_err(|_| TRUNCATED)?;
+        offset = end;
+
+        let ciphertext_len = read_u32(bytes, &mut offset)? as usize;
+        let end = offset.checked_add(ciphertext_len).ok_or(TRUNCATED)?;
+        let ciphertext = bytes.get(offset..end).ok_or(TRUNCATED)?.to_vec();
+
+        Ok(Self {
+            key_id,
+            iteration,
+            ciphertext,
+            leaf_index,
+            auth_path,
+            root_signature,
+        })
+    }
+}
+
+/// Encrypt a batch of plaintexts under the sender key's ratchet, signing the
+/// batch's Merkle root once instead of signing each outbound message
+/// individually. Each plaintext is still encrypted under its own ratcheted
+/// message key, so per-message forward secrecy is unchanged; only the
+/// signature is amortized across the batch.
+pub fn group_encrypt_batch<R: Rng + CryptoRng>(
+    sender_key_store: &mut dyn SenderKeyStore,
+    sender_key_id: &SenderKeyName,
+    plaintexts: &[&[u8]],
+    csprng: &mut R,
+) -> Result<Vec<Vec<u8>>> {
+    if plaintexts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut record = sender_key_store
+        .load_sender_key(&sender_key_id)?
+        .ok_or(SignalProtocolError::InvalidSenderKeyId)?;
+
+    let sender_key_state = record.sender_key_state()?;
+    let key_id = sender_key_state.sender_key_id()?;
+
+    let mut ciphertexts = Vec::with_capacity(plaintexts.len());
+    let mut iterations = Vec::with_capacity(plaintexts.len());
+    let mut leaves = Vec::with_capacity(plaintexts.len());
+
+    for plaintext in plaintexts {
+        let sender_key = sender_key_state.sender_chain_key()?.sender_message_key()?;
+        let ciphertext = crypto::aes_256_cbc_encrypt(
+            plaintext,
+            &sender_key.cipher_key()?,
+            &sender_key.iv()?,
+        )?;
+        let iteration = sender_key.iteration()?;
+
+        leaves.push(merkle_leaf_hash(key_id, &ciphertext, iteration)?);
+        iterations.push(iteration);
+        ciphertexts.push(ciphertext);
+
+        sender_key_state.set_sender_chain_key(sender_key_state.sender_chain_key()?.next()?)?;
+    }
+
+    let (root, paths) = merkle_tree(&leaves)?;
+
+    let signing_key = sender_key_state
+        .signing_key_private()?
+        .ok_or(SignalProtocolError::SenderKeySigningKeyMissing)?;
+    let root_signature = signing_key.calculate_signature(&root, csprng)?;
+
+    sender_key_store.store_sender_key(sender_key_id, &record)?;
+
+    ciphertexts
+        .into_iter()
+        .zip(iterations)
+        .zip(paths)
+        .enumerate()
+        .map(|(leaf_index, ((ciphertext, iteration), auth_path))| {
+            SenderKeyMerkleBatchMessage {
+                key_id,
+                iteration,
+                ciphertext,
+                leaf_index: leaf_index as u32,
+                auth_path,
+                root_signature,
+            }
+            .serialized()
+        })
+        .collect()
+}
+
+/// Decrypt a batch of [`SenderKeyMerkleBatchMessage`]s produced by the same
+/// `group_encrypt_batch` call. Each message's leaf hash is recomputed and
+/// walked up its authentication path; since every message in the batch
+/// should reach the same root, the batch's single signature is verified only
+/// once rather than once per message.
+pub fn group_decrypt_merkle_batch(
+    skm_bytes: &[&[u8]],
+    sender_key_store: &mut dyn SenderKeyStore,
+    sender_key_id: &SenderKeyName,
+) -> Result<Vec<Vec<u8>>> {
+    let mut record = sender_key_store
+        .load_sender_key(&sender_key_id)?
+        .ok_or(SignalProtocolError::InvalidSenderKeyId)?;
+
+    let messages = skm_bytes
+        .iter()
+        .map(|bytes| SenderKeyMerkleBatchMessage::try_from(*bytes))
+        .collect::<Result<Vec<_>>>()?;
+
+    if let Some(first) = messages.first() {
+        let sender_key_state = record.sender_key_state_for_keyid(first.key_id)?;
+        let signing_key = sender_key_state.signing_key_public()?;
+
+        let root = first.claimed_root()?;
+        for other in &messages[1..] {
+            // `key_id` is already folded into each leaf hash below, so a
+            // mismatched id would fail the root check on its own; this check
+            // just rejects the obviously-malformed case up front.
+            if other.key_id != first.key_id || other.claimed_root()? != root {
+                return Err(SignalProtocolError::SignatureValidationFailed);
+            }
+        }
+        if !signing_key.verify_signature(&root, &first.root_signature)? {
+            return Err(SignalProtocolError::SignatureValidationFailed);
+        }
+    }
+
+    let mut plaintexts = Vec::with_capacity(messages.len());
+    for skm in &messages {
+        let mut sender_key_state = record.sender_key_state_for_keyid(skm.key_id)?;
+        let sender_key = get_sender_key(&mut sender_key_state, skm.iteration)?;
+        plaintexts.push(crypto::aes_256_cbc_decrypt(
+            &skm.ciphertext,
+            &sender_key.cipher_key()?,
+            &sender_key.iv()?,
+        )?);
+    }
+    sender_key_store.store_sender_key(sender_key_id, &record)?;
+
+    Ok(plaintexts)
+}
+
+#[cfg(test)]
+mod merkle_batch_round_trip_tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    #[derive(Default)]
+    struct TestSenderKeyStore {
+        record: Option<SenderKeyRecord>,
+    }
+
+    impl SenderKeyStore for TestSenderKeyStore {
+        fn store_sender_key(
+            &mut self,
+            _sender_key_name: &SenderKeyName,
+            record: &SenderKeyRecord,
+        ) -> Result<()> {
+            self.record = Some(record.clone());
+            Ok(())
+        }
+
+        fn load_sender_key(
+            &mut self,
+            _sender_key_name: &SenderKeyName,
+        ) -> Result<Option<SenderKeyRecord>> {
+            Ok(self.record.clone())
+        }
+    }
+
+    #[test]
+    fn a_merkle_batch_round_trips_through_encrypt_and_decrypt() {
+        let address = crate::ProtocolAddress::new("+14151111111".to_string(), 1);
+        let sender_key_name = SenderKeyName::new("a-group".to_string(), address).unwrap();
+
+        let mut sender_store = TestSenderKeyStore::default();
+        let skdm =
+            create_sender_key_distribution_message(&sender_key_name, &mut sender_store, &mut OsRng)
+                .unwrap();
+        let mut recipient_store = TestSenderKeyStore::default();
+        process_sender_key_distribution_message(&sender_key_name, &skdm, &mut recipient_store)
+            .unwrap();
+
+        let plaintexts: Vec<&[u8]> = vec![b"one", b"two", b"three", b"four", b"five"];
+        let ciphertexts =
+            group_encrypt_batch(&mut sender_store, &sender_key_name, &plaintexts, &mut OsRng)
+                .unwrap();
+        let ciphertext_refs: Vec<&[u8]> = ciphertexts.iter().map(|c| c.as_slice()).collect();
+
+        let decrypted =
+            group_decrypt_merkle_batch(&ciphertext_refs, &mut recipient_store, &sender_key_name)
+                .unwrap();
+
+        assert_eq!(decrypted, plaintexts);
+    }
+
+    #[test]
+    fn a_tampered_ciphertext_fails_the_root_signature_check() {
+        let address = crate::ProtocolAddress::new("+14151111111".to_string(), 1);
+        let sender_key_name = SenderKeyName::new("a-group".to_string(), address).unwrap();
+
+        let mut sender_store = TestSenderKeyStore::default();
+        let skdm =
+            create_sender_key_distribution_message(&sender_key_name, &mut sender_store, &mut OsRng)
+                .unwrap();
+        let mut recipient_store = TestSenderKeyStore::default();
+        process_sender_key_distribution_message(&sender_key_name, &skdm, &mut recipient_store)
+            .unwrap();
+
+        let plaintexts: Vec<&[u8]> = vec![b"one", b"two", b"three"];
+        let mut ciphertexts =
+            group_encrypt_batch(&mut sender_store, &sender_key_name, &plaintexts, &mut OsRng)
+                .unwrap();
+        *ciphertexts[1].last_mut().unwrap() ^= 0x01;
+        let ciphertext_refs: Vec<&[u8]> = ciphertexts.iter().map(|c| c.as_slice()).collect();
+
+        let result =
+            group_decrypt_merkle_batch(&ciphertext_refs, &mut recipient_store, &sender_key_name);
+
+        assert!(result.is_err());
+    }
+}
+
 fn get_sender_key(state: &mut SenderKeyState, iteration: u32) -> Result<SenderMessageKey> {
     let sender_chain_key = state.sender_chain_key()?;
 
@@ -108,6 +516,555 @@ pub fn group_decrypt(
     Ok(plaintext)
 }
 
+/// Decrypt a batch of `SenderKeyMessage`s that all carry the same sender key
+/// id, verifying their signatures together with a single multi-scalar
+/// multiplication instead of one scalar-mult per message. This is the fast
+/// path for a client catching up on a backlog of queued messages after
+/// rejoining a busy group.
+///
+/// If the batch signature check fails (or the batch is empty or mixes key
+/// ids), falls back to verifying and decrypting each message individually so
+/// the one bad message can be isolated and reported as
+/// `SignatureValidationFailed`.
+pub fn group_decrypt_batch<R: Rng + CryptoRng>(
+    skm_bytes: &[&[u8]],
+    sender_key_store: &mut dyn SenderKeyStore,
+    sender_key_id: &SenderKeyName,
+    csprng: &mut R,
+) -> Result<Vec<Vec<u8>>> {
+    let messages = skm_bytes
+        .iter()
+        .map(|bytes| SenderKeyMessage::try_from(*bytes))
+        .collect::<Result<Vec<_>>>()?;
+
+    let same_key_id = !messages.is_empty()
+        && messages.windows(2).all(|w| w[0].key_id() == w[1].key_id());
+
+    if same_key_id {
+        let mut record = sender_key_store
+            .load_sender_key(&sender_key_id)?
+            .ok_or(SignalProtocolError::InvalidSenderKeyId)?;
+
+        let signing_key = record
+            .sender_key_state_for_keyid(messages[0].key_id())?
+            .signing_key_public()?;
+
+        if SenderKeyMessage::verify_signatures_batch(&messages, &signing_key, csprng)? {
+            let mut plaintexts = Vec::with_capacity(messages.len());
+            for skm in &messages {
+                let mut sender_key_state = record.sender_key_state_for_keyid(skm.key_id())?;
+                let sender_key = get_sender_key(&mut sender_key_state, skm.iteration())?;
+                plaintexts.push(crypto::aes_256_cbc_decrypt(
+                    skm.ciphertext(),
+                    &sender_key.cipher_key()?,
+                    &sender_key.iv()?,
+                )?);
+            }
+            sender_key_store.store_sender_key(sender_key_id, &record)?;
+            return Ok(plaintexts);
+        }
+    }
+
+    // Batch verification failed, or the batch couldn't be formed: fall back
+    // to the per-message path, which reports exactly which message is bad.
+    skm_bytes
+        .iter()
+        .map(|bytes| group_decrypt(bytes, sender_key_store, sender_key_id))
+        .collect()
+}
+
+/// Decompress a 32-byte Edwards point, rejecting non-canonical or small-order
+/// encodings. For Edwards points carried in signatures and proofs (signature
+/// `R` values, DKG/FROST/VRF points) and for hash-to-curve candidates — never
+/// for the crate's own XEdDSA public keys, which are Montgomery-form and need
+/// [`montgomery_public_to_edwards`] instead.
+fn decompress_point(bytes: &[u8]) -> Result<EdwardsPoint> {
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| SignalProtocolError::SignatureValidationFailed)?;
+    let point = CompressedEdwardsY(bytes)
+        .decompress()
+        .ok_or(SignalProtocolError::SignatureValidationFailed)?;
+    // `decompress` reduces `y` mod p, so a non-canonical encoding only shows
+    // up as a mismatch against the point's own re-encoding.
+    if point.compress().as_bytes() != &bytes || point.is_small_order() {
+        return Err(SignalProtocolError::SignatureValidationFailed);
+    }
+    Ok(point)
+}
+
+/// Convert the crate's XEdDSA Montgomery-form public key into its
+/// birationally-equivalent Edwards point, for use with the pure Ed25519
+/// Schnorr math in [`vrf`] and in [`SenderKeyMessage::verify_signatures_batch`].
+/// The sign bit the Montgomery encoding discards is fixed at 0 — XEdDSA signs
+/// by reinterpreting the same clamped scalar as an Edwards exponent and
+/// always negates the private key to make that sign bit 0, so this is the
+/// correct, not merely convenient, choice.
+fn montgomery_public_to_edwards(bytes: &[u8]) -> Result<EdwardsPoint> {
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| SignalProtocolError::SignatureValidationFailed)?;
+    MontgomeryPoint(bytes)
+        .to_edwards(0)
+        .ok_or(SignalProtocolError::SignatureValidationFailed)
+}
+
+impl SenderKeyMessage {
+    /// Verify many signatures at once using the standard batch Schnorr/EdDSA
+    /// equation: for signatures `(R_i, s_i)` over messages `M_i` under shared
+    /// signer key `A`, with `c_i = H(R_i || A || M_i)` and independent random
+    /// scalars `z_i`, accept all iff
+    /// `(Σ z_i·s_i)·B == Σ z_i·R_i + Σ (z_i·c_i)·A`, with the left side one
+    /// fixed-base multiplication and the right side a single multi-scalar
+    /// multiplication. `A` is converted from the crate's
+    /// Montgomery-form XEdDSA public key via [`montgomery_public_to_edwards`]
+    /// — the same conversion [`vrf`] uses — so this equation's `A` is the
+    /// same Edwards point `verify_signature`'s XEdDSA math checks against.
+    /// Returns `Ok(false)` (rather than isolating the bad message) on any
+    /// failure; callers fall back to per-message `verify_signature` to find
+    /// it.
+    pub fn verify_signatures_batch<R: Rng + CryptoRng>(
+        messages: &[SenderKeyMessage],
+        public_key: &PublicKey,
+        csprng: &mut R,
+    ) -> Result<bool> {
+        if messages.is_empty() {
+            return Ok(true);
+        }
+
+        let a = montgomery_public_to_edwards(&public_key.public_key_bytes()?)?;
+        let a_bytes = *a.compress().as_bytes();
+
+        let mut r_points = Vec::with_capacity(messages.len());
+        let mut s_scalars = Vec::with_capacity(messages.len());
+        let mut c_scalars = Vec::with_capacity(messages.len());
+
+        for skm in messages {
+            let (r_bytes, s_bytes) = skm.signature_parts();
+
+            let r = match decompress_point(r_bytes) {
+                Ok(r) => r,
+                Err(_) => return Ok(false),
+            };
+
+            let s = Scalar::from_canonical_bytes(
+                s_bytes
+                    .try_into()
+                    .map_err(|_| SignalProtocolError::SignatureValidationFailed)?,
+            );
+            let s = match s {
+                Some(s) => s,
+                None => return Ok(false),
+            };
+
+            let mut hash_input = Vec::with_capacity(r_bytes.len() + 32 + skm.signed_bytes().len());
+            hash_input.extend_from_slice(r_bytes);
+            hash_input.extend_from_slice(&a_bytes);
+            hash_input.extend_from_slice(skm.signed_bytes());
+            let c = Scalar::from_bytes_mod_order_wide(&crypto::sha512(&hash_input)?);
+
+            r_points.push(r);
+            s_scalars.push(s);
+            c_scalars.push(c);
+        }
+
+        // Independent 128-bit random weights keep the forgery probability
+        // negligible (~2^-128) while staying cheap to sample and multiply.
+        let z_scalars: Vec<Scalar> = (0..messages.len())
+            .map(|_| {
+                let mut wide = [0u8; 32];
+                csprng.fill(&mut wide[..16]);
+                Scalar::from_bytes_mod_order(wide)
+            })
+            .collect();
+
+        let lhs_scalar: Scalar = z_scalars
+            .iter()
+            .zip(s_scalars.iter())
+            .map(|(z, s)| z * s)
+            .sum();
+        let lhs = &lhs_scalar * &ED25519_BASEPOINT_TABLE;
+
+        let zc_sum: Scalar = z_scalars
+            .iter()
+            .zip(c_scalars.iter())
+            .map(|(z, c)| z * c)
+            .sum();
+        let rhs = EdwardsPoint::vartime_multiscalar_mul(
+            z_scalars.iter().chain(std::iter::once(&zc_sum)),
+            r_points.iter().chain(std::iter::once(&a)),
+        );
+
+        Ok(lhs == rhs)
+    }
+}
+
+/// The Fiat-Shamir challenge `H(R || A || m)` used by the plain Schnorr
+/// helpers below, reduced mod the group
+/// order so it can be used directly as a scalar.
+fn schnorr_challenge(r: &EdwardsPoint, public_key: &EdwardsPoint, message: &[u8]) -> Result<Scalar> {
+    let mut input = Vec::with_capacity(64 + message.len());
+    input.extend_from_slice(r.compress().as_bytes());
+    input.extend_from_slice(public_key.compress().as_bytes());
+    input.extend_from_slice(message);
+    Ok(Scalar::from_bytes_mod_order_wide(&crypto::sha512(&input)?))
+}
+
+/// A bare Schnorr signature over `message` under `secret`/`public_key`:
+/// `R = k·G`, `s = k + c·secret`. Used as-is for DKG proofs-of-possession and
+/// as the final verification equation for FROST-aggregated signatures.
+fn schnorr_sign<R: Rng + CryptoRng>(
+    secret: &Scalar,
+    public_key: &EdwardsPoint,
+    message: &[u8],
+    csprng: &mut R,
+) -> Result<[u8; 64]> {
+    let k = Scalar::random(csprng);
+    let r = &k * &ED25519_BASEPOINT_TABLE;
+    let c = schnorr_challenge(&r, public_key, message)?;
+    let s = k + c * secret;
+
+    let mut out = [0u8; 64];
+    out[..32].copy_from_slice(r.compress().as_bytes());
+    out[32..].copy_from_slice(s.as_bytes());
+    Ok(out)
+}
+
+fn schnorr_verify(public_key: &EdwardsPoint, message: &[u8], signature: &[u8; 64]) -> Result<bool> {
+    let r = match decompress_point(&signature[..32]) {
+        Ok(r) => r,
+        Err(_) => return Ok(false),
+    };
+    let s_bytes: [u8; 32] = signature[32..]
+        .try_into()
+        .map_err(|_| SignalProtocolError::SignatureValidationFailed)?;
+    let s = match Scalar::from_canonical_bytes(s_bytes) {
+        Some(s) => s,
+        None => return Ok(false),
+    };
+
+    let c = schnorr_challenge(&r, public_key, message)?;
+    Ok(&s * &ED25519_BASEPOINT_TABLE == r + c * public_key)
+}
+
+/// SimplPedPoP: a dealerless DKG establishing one signing key shared by the
+/// whole group, via per-participant Feldman VSS plus a proof-of-possession.
+///
+/// `group_verifying_key`'s output is an admin-quorum verification key for
+/// [`frost`]'s threshold co-signing of SKDMs
+/// ([`process_sender_key_distribution_message_with_admin_authorization`]),
+/// not a replacement for the per-sender XEdDSA key `group_encrypt`/
+/// `group_decrypt` sign ordinary messages with. Signing every message under
+/// the DKG-shared key would mean a live multi-round FROST session per
+/// message instead of per SKDM, which defeats the point of a ratcheted
+/// per-sender chain key; deliberately out of scope here.
+pub mod dkg {
+    use super::*;
+
+    /// What participant `i` publishes to the rest of the group: commitments
+    /// to their polynomial's coefficients, plus a proof-of-possession of the
+    /// constant term so others can't claim a commitment they can't open.
+    pub struct DkgCommitments {
+        pub coefficients: Vec<EdwardsPoint>,
+        pub proof_of_possession: [u8; 64],
+    }
+
+    /// One participant's private state during a DKG round.
+    pub struct DkgParticipant {
+        coefficients: Vec<Scalar>,
+    }
+
+    impl DkgParticipant {
+        /// Sample a fresh degree-`threshold` polynomial `f(x) = a_0 + ... +
+        /// a_threshold·x^threshold`.
+        pub fn generate<R: Rng + CryptoRng>(threshold: usize, csprng: &mut R) -> Self {
+            let coefficients = (0..=threshold).map(|_| Scalar::random(csprng)).collect();
+            Self { coefficients }
+        }
+
+        fn evaluate(&self, x: Scalar) -> Scalar {
+            self.coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::ZERO, |acc, a| acc * x + a)
+        }
+
+        /// Commitments to publish to the rest of the group, plus a Schnorr
+        /// proof-of-possession over the constant term `a_0`.
+        pub fn commit<R: Rng + CryptoRng>(&self, csprng: &mut R) -> Result<DkgCommitments> {
+            let coefficients: Vec<EdwardsPoint> = self
+                .coefficients
+                .iter()
+                .map(|a| a * &ED25519_BASEPOINT_TABLE)
+                .collect();
+
+            let a0 = self.coefficients[0];
+            let public_a0 = coefficients[0];
+            let proof_of_possession =
+                schnorr_sign(&a0, &public_a0, public_a0.compress().as_bytes(), csprng)?;
+
+            Ok(DkgCommitments {
+                coefficients,
+                proof_of_possession,
+            })
+        }
+
+        /// The share `f(j)` to send privately to participant `j`
+        /// (1-indexed, matching `j` in [`verify_share`]). `j` must be
+        /// nonzero: `f(0) == a_0` is the dealer's own secret constant term,
+        /// not a blinded share.
+        pub fn share_for(&self, participant: u32) -> Result<Scalar> {
+            validate_participant_id(participant)?;
+            Ok(self.evaluate(Scalar::from(participant as u64)))
+        }
+    }
+
+    /// Participant ids double as evaluation points `x` in the Shamir
+    /// polynomial, so `0` (which would hand out the dealer's raw secret
+    /// constant term `f(0) == a_0`) is never a valid id.
+    fn validate_participant_id(participant: u32) -> Result<()> {
+        if participant == 0 {
+            return Err(SignalProtocolError::SenderKeyShareVerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Verify a share `f_i(j)` received from participant `i`'s published
+    /// commitments: `f_i(j)·G == Σ_k j^k·C_ik`. Also checks `i`'s
+    /// proof-of-possession and that `i` published exactly `threshold + 1`
+    /// coefficients — the degree every participant agreed to — so a
+    /// dishonest dealer can't lower their own polynomial's degree (and so
+    /// weaken the group key's reconstruction threshold) while still handing
+    /// out shares that verify.
+    pub fn verify_share(
+        threshold: usize,
+        sender_commitments: &DkgCommitments,
+        participant: u32,
+        share: Scalar,
+    ) -> Result<()> {
+        validate_participant_id(participant)?;
+
+        if sender_commitments.coefficients.len() != threshold + 1 {
+            return Err(SignalProtocolError::SenderKeyShareVerificationFailed);
+        }
+
+        let public_a0 = *sender_commitments
+            .coefficients
+            .first()
+            .ok_or(SignalProtocolError::SenderKeyShareVerificationFailed)?;
+        let pop_ok = schnorr_verify(
+            &public_a0,
+            public_a0.compress().as_bytes(),
+            &sender_commitments.proof_of_possession,
+        )?;
+        if !pop_ok {
+            return Err(SignalProtocolError::SenderKeyShareVerificationFailed);
+        }
+
+        let x = Scalar::from(participant as u64);
+        let mut x_pow = Scalar::ONE;
+        let mut expected = EdwardsPoint::identity();
+        for commitment in &sender_commitments.coefficients {
+            expected += x_pow * commitment;
+            x_pow *= x;
+        }
+
+        if &share * &ED25519_BASEPOINT_TABLE == expected {
+            Ok(())
+        } else {
+            Err(SignalProtocolError::SenderKeyShareVerificationFailed)
+        }
+    }
+
+    /// The group's shared verification key `Y = Σ_i C_{i0}`, once every
+    /// participant's commitments have been collected and verified. Rejects
+    /// the round if any participant's commitment vector doesn't match the
+    /// agreed `threshold`, for the same reason [`verify_share`] does: a
+    /// mismatched degree is a dishonest dealer weakening the group's
+    /// reconstruction threshold, not a message a single `verify_share` call
+    /// against that dealer would necessarily have caught first.
+    pub fn group_verifying_key(
+        threshold: usize,
+        all_commitments: &[DkgCommitments],
+    ) -> Result<EdwardsPoint> {
+        if all_commitments
+            .iter()
+            .any(|c| c.coefficients.len() != threshold + 1)
+        {
+            return Err(SignalProtocolError::SenderKeyShareVerificationFailed);
+        }
+
+        Ok(all_commitments
+            .iter()
+            .filter_map(|c| c.coefficients.first())
+            .fold(EdwardsPoint::identity(), |acc, c| acc + c))
+    }
+
+    /// A participant's long-term secret share `s_j = Σ_i f_i(j)`, combining
+    /// the shares received from every participant (including their own).
+    pub fn combine_shares(shares: &[Scalar]) -> Scalar {
+        shares.iter().sum()
+    }
+}
+
+/// FROST: two-round threshold Schnorr co-signing over the [`dkg`]-derived
+/// group key, for requiring a t-of-n admin quorum on a new SKDM.
+pub mod frost {
+    use super::*;
+
+    /// Round-one output: an admin's public nonce commitments `(D_i, E_i)`,
+    /// to be collected from every admin in the signing set before round two.
+    pub struct NonceCommitment {
+        pub hiding: EdwardsPoint,
+        pub binding: EdwardsPoint,
+    }
+
+    /// An admin's private round-one nonces `(d_i, e_i)`, kept until round two
+    /// and then discarded; reusing them across signatures leaks the admin's
+    /// secret share. [`sign`] takes `Self` by value rather than by reference
+    /// so a caller can't accidentally call it twice with the same pair.
+    pub struct SigningNonces {
+        hiding: Scalar,
+        binding: Scalar,
+    }
+
+    impl SigningNonces {
+        pub fn generate<R: Rng + CryptoRng>(csprng: &mut R) -> (Self, NonceCommitment) {
+            let hiding = Scalar::random(csprng);
+            let binding = Scalar::random(csprng);
+            let commitment = NonceCommitment {
+                hiding: &hiding * &ED25519_BASEPOINT_TABLE,
+                binding: &binding * &ED25519_BASEPOINT_TABLE,
+            };
+            (Self { hiding, binding }, commitment)
+        }
+    }
+
+    /// Binding factor `ρ_i = H(i, m, B)`, computed from the full commitment
+    /// list `B` so every admin agrees on the same value for participant `i`.
+    fn binding_factor(
+        participant: u32,
+        message: &[u8],
+        commitments: &[(u32, NonceCommitment)],
+    ) -> Result<Scalar> {
+        let mut input = Vec::new();
+        input.extend_from_slice(&participant.to_be_bytes());
+        input.extend_from_slice(message);
+        for (id, c) in commitments {
+            input.extend_from_slice(&id.to_be_bytes());
+            input.extend_from_slice(c.hiding.compress().as_bytes());
+            input.extend_from_slice(c.binding.compress().as_bytes());
+        }
+        Ok(Scalar::from_bytes_mod_order_wide(&crypto::sha512(&input)?))
+    }
+
+    /// Group commitment `R = Σ_i (D_i + ρ_i·E_i)`.
+    fn group_commitment(
+        message: &[u8],
+        commitments: &[(u32, NonceCommitment)],
+    ) -> Result<EdwardsPoint> {
+        let mut r = EdwardsPoint::identity();
+        for (id, c) in commitments {
+            let rho_i = binding_factor(*id, message, commitments)?;
+            r += c.hiding + rho_i * c.binding;
+        }
+        Ok(r)
+    }
+
+    /// Participant ids are Shamir evaluation points, so a signing set with a
+    /// zero id or a repeated id would hand `lagrange_coefficient` either the
+    /// dealer's raw secret point or an incorrect interpolation.
+    fn validate_signing_set(signing_set: &[u32]) -> Result<()> {
+        if signing_set.iter().any(|&id| id == 0) {
+            return Err(SignalProtocolError::SenderKeyShareVerificationFailed);
+        }
+        let mut sorted = signing_set.to_vec();
+        sorted.sort_unstable();
+        if sorted.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(SignalProtocolError::SenderKeyShareVerificationFailed);
+        }
+        Ok(())
+    }
+
+    /// Lagrange coefficient for participant `i`, interpolating the signing
+    /// set's shares at `x = 0`: `λ_i = Π_{j≠i} j / (j - i)`.
+    pub fn lagrange_coefficient(participant: u32, signing_set: &[u32]) -> Scalar {
+        let xi = Scalar::from(participant as u64);
+        signing_set
+            .iter()
+            .filter(|&&j| j != participant)
+            .fold(Scalar::ONE, |acc, &j| {
+                let xj = Scalar::from(j as u64);
+                acc * xj * (xj - xi).invert()
+            })
+    }
+
+    pub struct PartialSignature {
+        pub participant: u32,
+        pub z: Scalar,
+    }
+
+    /// Round two: given the full commitment list and the group's shared
+    /// verifying key `Y`, admin `participant` computes their partial
+    /// response `z_i = d_i + e_i·ρ_i + λ_i·s_i·c`.
+    pub fn sign(
+        participant: u32,
+        nonces: SigningNonces,
+        secret_share: Scalar,
+        signing_set: &[u32],
+        commitments: &[(u32, NonceCommitment)],
+        group_verifying_key: &EdwardsPoint,
+        message: &[u8],
+    ) -> Result<PartialSignature> {
+        validate_signing_set(signing_set)?;
+
+        let rho_i = binding_factor(participant, message, commitments)?;
+        let r = group_commitment(message, commitments)?;
+        let c = schnorr_challenge(&r, group_verifying_key, message)?;
+        let lambda_i = lagrange_coefficient(participant, signing_set);
+
+        let z = nonces.hiding + nonces.binding * rho_i + lambda_i * secret_share * c;
+        Ok(PartialSignature { participant, z })
+    }
+
+    /// Coordinator step: aggregate every admin's partial response into one
+    /// ordinary Schnorr signature `(R, z)`, verifiable with plain
+    /// `schnorr_verify` against the group's shared verifying key.
+    pub fn aggregate(
+        message: &[u8],
+        commitments: &[(u32, NonceCommitment)],
+        partials: &[PartialSignature],
+    ) -> Result<[u8; 64]> {
+        let r = group_commitment(message, commitments)?;
+        let z: Scalar = partials.iter().map(|p| p.z).sum();
+
+        let mut out = [0u8; 64];
+        out[..32].copy_from_slice(r.compress().as_bytes());
+        out[32..].copy_from_slice(z.as_bytes());
+        Ok(out)
+    }
+}
+
+/// Like [`process_sender_key_distribution_message`], but for groups that
+/// opt into admin-controlled membership: the SKDM is only accepted if
+/// `threshold_signature` is a valid FROST signature from a t-of-n quorum of
+/// admins over the SKDM's own bytes, verified against the group's shared
+/// verifying key from the [`dkg`] round. This stops a single compromised
+/// device from unilaterally injecting a new sender-key state.
+pub fn process_sender_key_distribution_message_with_admin_authorization(
+    sender_key_name: &SenderKeyName,
+    skdm: &SenderKeyDistributionMessage,
+    admin_group_verifying_key: &EdwardsPoint,
+    threshold_signature: &[u8; 64],
+    sender_key_store: &mut dyn SenderKeyStore,
+) -> Result<()> {
+    if !schnorr_verify(admin_group_verifying_key, skdm.serialized()?, threshold_signature)? {
+        return Err(SignalProtocolError::SenderKeyDistributionNotAuthorized);
+    }
+
+    process_sender_key_distribution_message(sender_key_name, skdm, sender_key_store)
+}
+
 pub fn process_sender_key_distribution_message(
     sender_key_name: &SenderKeyName,
     skdm: &SenderKeyDistributionMessage,
@@ -162,4 +1119,751 @@ pub fn create_sender_key_distribution_message<R: Rng + CryptoRng>(
         &sender_chain_key.seed()?,
         state.signing_key_public()?,
     )
+}
+
+/// A Schnorr VRF over `chain_key` seeds, so a recipient can catch a sender
+/// handing different SKDM seeds to different group members.
+pub mod vrf {
+    use super::*;
+
+    /// A VRF output together with its DLEQ proof, as shipped inside an SKDM.
+    pub struct VrfProof {
+        beta: EdwardsPoint,
+        c: Scalar,
+        s: Scalar,
+    }
+
+    impl VrfProof {
+        pub fn to_bytes(&self) -> [u8; 96] {
+            let mut out = [0u8; 96];
+            out[..32].copy_from_slice(self.beta.compress().as_bytes());
+            out[32..64].copy_from_slice(self.c.as_bytes());
+            out[64..].copy_from_slice(self.s.as_bytes());
+            out
+        }
+
+        pub fn from_bytes(bytes: &[u8; 96]) -> Result<Self> {
+            let beta = decompress_point(&bytes[..32])?;
+            let c = Scalar::from_canonical_bytes(bytes[32..64].try_into().unwrap())
+                .ok_or(SignalProtocolError::SenderKeySeedVrfInvalid)?;
+            let s = Scalar::from_canonical_bytes(bytes[64..].try_into().unwrap())
+                .ok_or(SignalProtocolError::SenderKeySeedVrfInvalid)?;
+            Ok(Self { beta, c, s })
+        }
+    }
+
+    /// The `(group id, sender key id)` context label the VRF is bound to, so
+    /// two members comparing SKDMs for the same key id are comparing VRF
+    /// outputs evaluated on the same input.
+    pub fn context_label(group_id: &[u8], sender_key_id: u32) -> Vec<u8> {
+        let mut label = Vec::with_capacity(group_id.len() + 4);
+        label.extend_from_slice(group_id);
+        label.extend_from_slice(&sender_key_id.to_be_bytes());
+        label
+    }
+
+    /// Hash-and-increment a context label to a curve point `H`, so the VRF
+    /// doesn't rely on a dedicated hash-to-curve implementation.
+    fn hash_to_curve(context: &[u8]) -> Result<EdwardsPoint> {
+        for counter in 0u8..=255 {
+            let mut input = Vec::with_capacity(context.len() + 1);
+            input.extend_from_slice(context);
+            input.push(counter);
+            let digest = crypto::sha512(&input)?;
+            if let Ok(point) = decompress_point(&digest[..32]) {
+                return Ok(point);
+            }
+        }
+        Err(SignalProtocolError::SenderKeySeedVrfInvalid)
+    }
+
+    fn dleq_challenge(
+        public_key: &EdwardsPoint,
+        gamma: &EdwardsPoint,
+        u: &EdwardsPoint,
+        v: &EdwardsPoint,
+    ) -> Result<Scalar> {
+        let mut input = Vec::with_capacity(32 * 4);
+        input.extend_from_slice(public_key.compress().as_bytes());
+        input.extend_from_slice(gamma.compress().as_bytes());
+        input.extend_from_slice(u.compress().as_bytes());
+        input.extend_from_slice(v.compress().as_bytes());
+        Ok(Scalar::from_bytes_mod_order_wide(&crypto::sha512(&input)?))
+    }
+
+    /// Evaluate the VRF on `context` under secret scalar `x` (whose public
+    /// key is `public_key = x·G`): output `Gamma = x·H`, with a Schnorr DLEQ
+    /// proof that `Gamma` and `public_key` share the discrete log `x`.
+    pub fn evaluate<R: Rng + CryptoRng>(
+        context: &[u8],
+        x: &Scalar,
+        public_key: &EdwardsPoint,
+        csprng: &mut R,
+    ) -> Result<VrfProof> {
+        let h = hash_to_curve(context)?;
+        let gamma = x * h;
+
+        let k = Scalar::random(csprng);
+        let u = &k * &ED25519_BASEPOINT_TABLE;
+        let v = k * h;
+
+        let c = dleq_challenge(public_key, &gamma, &u, &v)?;
+        let s = k + c * x;
+
+        Ok(VrfProof { beta: gamma, c, s })
+    }
+
+    /// Verify `proof` against `public_key` for `context`, returning the
+    /// 32-byte chain seed derived from the VRF output on success. Two
+    /// honest SKDMs for the same `(group, key id)` always verify to the
+    /// same seed; an equivocating sender's split-view SKDMs either fail
+    /// this check or (if consistent) are provably not equivocating.
+    pub fn verify(context: &[u8], public_key: &EdwardsPoint, proof: &VrfProof) -> Result<[u8; 32]> {
+        let h = hash_to_curve(context)?;
+
+        let u = &proof.s * &ED25519_BASEPOINT_TABLE - proof.c * public_key;
+        let v = proof.s * h - proof.c * proof.beta;
+
+        let expected_c = dleq_challenge(public_key, &proof.beta, &u, &v)?;
+        if expected_c != proof.c {
+            return Err(SignalProtocolError::SenderKeySeedVrfInvalid);
+        }
+
+        seed_from_output(&proof.beta)
+    }
+
+    fn seed_from_output(beta: &EdwardsPoint) -> Result<[u8; 32]> {
+        let mut input = Vec::with_capacity(32 + 16);
+        input.extend_from_slice(b"WhisperGroupVrfSeed");
+        input.extend_from_slice(beta.compress().as_bytes());
+        let digest = crypto::sha512(&input)?;
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&digest[..32]);
+        Ok(seed)
+    }
+}
+
+/// Like [`create_sender_key_distribution_message`], but derives the chain
+/// seed from a VRF evaluation over `(group_id, sender_key_id)` and returns
+/// its proof alongside the SKDM.
+pub fn create_sender_key_distribution_message_with_vrf_seed<R: Rng + CryptoRng>(
+    sender_key_name: &SenderKeyName,
+    group_id: &[u8],
+    sender_key_store: &mut dyn SenderKeyStore,
+    csprng: &mut R,
+) -> Result<(SenderKeyDistributionMessage, [u8; 96])> {
+    let mut sender_key_record = sender_key_store
+        .load_sender_key(sender_key_name)?
+        .unwrap_or_else(SenderKeyRecord::new_empty);
+
+    let mut vrf_proof = sender_key_record
+        .vrf_proof()?
+        .map(|bytes| vrf::VrfProof::from_bytes(&bytes))
+        .transpose()?;
+
+    if sender_key_record.is_empty()? {
+        // libsignal-protocol-java uses 31-bit integers for sender key IDs
+        let sender_key_id = (csprng.gen::<u32>()) >> 1;
+        let iteration = 0;
+        let signing_key = curve::KeyPair::generate(csprng);
+
+        // XEdDSA reuses the clamped X25519 scalar directly as an Edwards
+        // exponent; it is never a canonical (< ℓ) Ed25519 scalar, so
+        // `from_canonical_bytes`/`from_bytes_mod_order` would reject or
+        // silently change it. `from_bits` keeps the exact clamped value.
+        let mut signing_scalar = Scalar::from_bits(signing_key.private_key.serialize());
+        let signing_public_point =
+            montgomery_public_to_edwards(&signing_key.public_key.public_key_bytes()?)?;
+
+        // `montgomery_public_to_edwards` always decodes with sign bit 0, but
+        // `signing_scalar * G` may land on the point with sign bit 1 (the
+        // Montgomery encoding discards the sign, so both are valid decodings
+        // of the same u-coordinate). XEdDSA signing handles this by negating
+        // the private scalar whenever that happens, so the scalar it signs
+        // with always corresponds to the sign-0 point; this must match that
+        // convention; or `signing_scalar * G != signing_public_point` and
+        // the self-check below fails for roughly half of all generated keys.
+        let raw_point = &signing_scalar * &ED25519_BASEPOINT_TABLE;
+        if raw_point.compress().as_bytes()[31] & 0x80 != 0 {
+            signing_scalar = -signing_scalar;
+        }
+
+        let context = vrf::context_label(group_id, sender_key_id);
+        let proof = vrf::evaluate(&context, &signing_scalar, &signing_public_point, csprng)?;
+        let sender_key = vrf::verify(&context, &signing_public_point, &proof)?;
+
+        sender_key_record.set_sender_key_state(
+            sender_key_id,
+            iteration,
+            &sender_key,
+            signing_key.public_key,
+            Some(signing_key.private_key),
+        )?;
+        sender_key_record.set_vrf_proof(proof.to_bytes())?;
+        sender_key_store.store_sender_key(sender_key_name, &sender_key_record)?;
+        vrf_proof = Some(proof);
+    }
+
+    let state = sender_key_record.sender_key_state()?;
+    let sender_chain_key = state.sender_chain_key()?;
+
+    let skdm = SenderKeyDistributionMessage::new(
+        state.sender_key_id()?,
+        sender_chain_key.iteration()?,
+        &sender_chain_key.seed()?,
+        state.signing_key_public()?,
+    )?;
+
+    let vrf_proof = vrf_proof.ok_or(SignalProtocolError::SenderKeySeedVrfInvalid)?;
+    Ok((skdm, vrf_proof.to_bytes()))
+}
+
+/// Like [`process_sender_key_distribution_message`], but rejects the SKDM
+/// unless its `chain_key` is the genuine VRF output for `(group_id, skdm.id())`.
+pub fn process_sender_key_distribution_message_with_vrf_seed(
+    sender_key_name: &SenderKeyName,
+    skdm: &SenderKeyDistributionMessage,
+    group_id: &[u8],
+    vrf_proof_bytes: &[u8; 96],
+    sender_key_store: &mut dyn SenderKeyStore,
+) -> Result<()> {
+    let proof = vrf::VrfProof::from_bytes(vrf_proof_bytes)?;
+    let signing_key_point = montgomery_public_to_edwards(&skdm.signing_key()?.public_key_bytes()?)?;
+
+    let context = vrf::context_label(group_id, skdm.id()?);
+    let expected_seed = vrf::verify(&context, &signing_key_point, &proof)?;
+
+    if expected_seed != *skdm.chain_key()? {
+        return Err(SignalProtocolError::SenderKeySeedVrfInvalid);
+    }
+
+    process_sender_key_distribution_message(sender_key_name, skdm, sender_key_store)
+}
+
+#[cfg(test)]
+mod group_decrypt_batch_tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    #[derive(Default)]
+    struct TestSenderKeyStore {
+        record: Option<SenderKeyRecord>,
+    }
+
+    impl SenderKeyStore for TestSenderKeyStore {
+        fn store_sender_key(
+            &mut self,
+            _sender_key_name: &SenderKeyName,
+            record: &SenderKeyRecord,
+        ) -> Result<()> {
+            self.record = Some(record.clone());
+            Ok(())
+        }
+
+        fn load_sender_key(
+            &mut self,
+            _sender_key_name: &SenderKeyName,
+        ) -> Result<Option<SenderKeyRecord>> {
+            Ok(self.record.clone())
+        }
+    }
+
+    fn distribute(
+        sender_key_name: &SenderKeyName,
+        sender_store: &mut TestSenderKeyStore,
+        recipient_store: &mut TestSenderKeyStore,
+    ) {
+        let skdm =
+            create_sender_key_distribution_message(sender_key_name, sender_store, &mut OsRng)
+                .unwrap();
+        process_sender_key_distribution_message(sender_key_name, &skdm, recipient_store).unwrap();
+    }
+
+    // Exercises the real single-MSM batch-verification path in
+    // `SenderKeyMessage::verify_signatures_batch`/`group_decrypt_batch`
+    // (rather than only its math in isolation), through the actual sender
+    // and recipient store round trip the crate's callers use.
+    #[test]
+    fn a_batch_of_honest_messages_decrypts_via_the_msm_path() {
+        let address = crate::ProtocolAddress::new("+14151111111".to_string(), 1);
+        let sender_key_name = SenderKeyName::new("a-group".to_string(), address).unwrap();
+
+        let mut sender_store = TestSenderKeyStore::default();
+        let mut recipient_store = TestSenderKeyStore::default();
+        distribute(&sender_key_name, &mut sender_store, &mut recipient_store);
+
+        let plaintexts: Vec<Vec<u8>> = (0..8)
+            .map(|i| format!("message {}", i).into_bytes())
+            .collect();
+        let ciphertexts: Vec<Vec<u8>> = plaintexts
+            .iter()
+            .map(|p| group_encrypt(&mut sender_store, &sender_key_name, p, &mut OsRng).unwrap())
+            .collect();
+        let ciphertext_refs: Vec<&[u8]> = ciphertexts.iter().map(|c| c.as_slice()).collect();
+
+        let decrypted = group_decrypt_batch(
+            &ciphertext_refs,
+            &mut recipient_store,
+            &sender_key_name,
+            &mut OsRng,
+        )
+        .unwrap();
+
+        assert_eq!(decrypted, plaintexts);
+    }
+
+    // A tampered message must not be silently accepted by the MSM check, and
+    // the per-message fallback must still report exactly which one is bad.
+    #[test]
+    fn a_tampered_message_fails_the_batch_and_the_fallback() {
+        let address = crate::ProtocolAddress::new("+14151111111".to_string(), 1);
+        let sender_key_name = SenderKeyName::new("a-group".to_string(), address).unwrap();
+
+        let mut sender_store = TestSenderKeyStore::default();
+        let mut recipient_store = TestSenderKeyStore::default();
+        distribute(&sender_key_name, &mut sender_store, &mut recipient_store);
+
+        let mut ciphertexts: Vec<Vec<u8>> = (0..4)
+            .map(|i| {
+                group_encrypt(
+                    &mut sender_store,
+                    &sender_key_name,
+                    format!("message {}", i).as_bytes(),
+                    &mut OsRng,
+                )
+                .unwrap()
+            })
+            .collect();
+        let last = ciphertexts.len() - 1;
+        *ciphertexts[last].last_mut().unwrap() ^= 0x01;
+        let ciphertext_refs: Vec<&[u8]> = ciphertexts.iter().map(|c| c.as_slice()).collect();
+
+        let result = group_decrypt_batch(
+            &ciphertext_refs,
+            &mut recipient_store,
+            &sender_key_name,
+            &mut OsRng,
+        );
+
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod decompress_point_tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn roundtrips_a_freshly_generated_point() {
+        let scalar = Scalar::random(&mut OsRng);
+        let point = &scalar * &ED25519_BASEPOINT_TABLE;
+        let decompressed = decompress_point(point.compress().as_bytes()).unwrap();
+        assert_eq!(decompressed.compress(), point.compress());
+    }
+
+    #[test]
+    fn rejects_a_non_canonical_encoding() {
+        let bytes = [0xffu8; 32];
+        assert!(decompress_point(&bytes).is_err());
+    }
+
+    #[test]
+    fn rejects_the_identity_point_as_small_order() {
+        let identity = EdwardsPoint::identity();
+        assert!(decompress_point(identity.compress().as_bytes()).is_err());
+    }
+}
+
+#[cfg(test)]
+mod merkle_batch_tests {
+    use super::*;
+
+    fn leaves() -> Vec<[u8; 32]> {
+        (0..5u32)
+            .map(|i| merkle_leaf_hash(7, format!("message {}", i).as_bytes(), i).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn every_leaf_authenticates_against_the_root() {
+        let leaves = leaves();
+        let (root, paths) = merkle_tree(&leaves).unwrap();
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let recomputed = merkle_root_from_path(*leaf, i as u32, &paths[i]).unwrap();
+            assert_eq!(recomputed, root);
+        }
+    }
+
+    #[test]
+    fn a_tampered_leaf_does_not_authenticate() {
+        let leaves = leaves();
+        let (root, paths) = merkle_tree(&leaves).unwrap();
+
+        let mut tampered_leaf = leaves[2];
+        tampered_leaf[0] ^= 0x01;
+        let recomputed = merkle_root_from_path(tampered_leaf, 2, &paths[2]).unwrap();
+        assert_ne!(recomputed, root);
+    }
+
+    #[test]
+    fn a_leaf_hash_binds_its_key_id() {
+        let ciphertext = b"message 0";
+        let a = merkle_leaf_hash(7, ciphertext, 0).unwrap();
+        let b = merkle_leaf_hash(8, ciphertext, 0).unwrap();
+        assert_ne!(a, b);
+    }
+}
+
+#[cfg(test)]
+mod dkg_tests {
+    use super::dkg::*;
+    use super::*;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn an_honest_share_verifies_against_its_sender_commitments() {
+        let sender = DkgParticipant::generate(1, &mut OsRng);
+        let commitments = sender.commit(&mut OsRng).unwrap();
+        let share = sender.share_for(2).unwrap();
+
+        assert!(verify_share(1, &commitments, 2, share).is_ok());
+    }
+
+    #[test]
+    fn a_tampered_share_fails_verification() {
+        let sender = DkgParticipant::generate(1, &mut OsRng);
+        let commitments = sender.commit(&mut OsRng).unwrap();
+        let tampered_share = sender.share_for(2).unwrap() + Scalar::ONE;
+
+        assert!(verify_share(1, &commitments, 2, tampered_share).is_err());
+    }
+
+    #[test]
+    fn share_for_participant_zero_is_rejected() {
+        let sender = DkgParticipant::generate(1, &mut OsRng);
+        assert!(sender.share_for(0).is_err());
+    }
+
+    #[test]
+    fn verify_share_for_participant_zero_is_rejected() {
+        let sender = DkgParticipant::generate(1, &mut OsRng);
+        let commitments = sender.commit(&mut OsRng).unwrap();
+        assert!(verify_share(1, &commitments, 0, Scalar::ONE).is_err());
+    }
+
+    #[test]
+    fn verify_share_rejects_a_mismatched_threshold() {
+        let sender = DkgParticipant::generate(1, &mut OsRng);
+        let commitments = sender.commit(&mut OsRng).unwrap();
+        let share = sender.share_for(2).unwrap();
+
+        // These commitments were published for threshold 1 (2 coefficients);
+        // claiming threshold 2 (3 coefficients) must be rejected even though
+        // the share itself is honestly computed.
+        assert!(verify_share(2, &commitments, 2, share).is_err());
+    }
+
+    #[test]
+    fn group_verifying_key_sums_every_participants_constant_term() {
+        let participants: Vec<_> = (0..3)
+            .map(|_| DkgParticipant::generate(1, &mut OsRng))
+            .collect();
+        let commitments: Vec<_> = participants
+            .iter()
+            .map(|p| p.commit(&mut OsRng).unwrap())
+            .collect();
+
+        let expected = commitments
+            .iter()
+            .fold(EdwardsPoint::identity(), |acc, c| acc + c.coefficients[0]);
+        assert_eq!(
+            group_verifying_key(1, &commitments).unwrap().compress(),
+            expected.compress()
+        );
+    }
+
+    #[test]
+    fn group_verifying_key_rejects_a_mismatched_threshold_commitment() {
+        let honest: Vec<_> = (0..2)
+            .map(|_| DkgParticipant::generate(1, &mut OsRng))
+            .collect();
+        let mut commitments: Vec<_> = honest.iter().map(|p| p.commit(&mut OsRng).unwrap()).collect();
+
+        let dishonest = DkgParticipant::generate(2, &mut OsRng);
+        commitments.push(dishonest.commit(&mut OsRng).unwrap());
+
+        assert!(group_verifying_key(1, &commitments).is_err());
+    }
+}
+
+#[cfg(test)]
+mod frost_tests {
+    use super::frost::*;
+    use super::*;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn a_2_of_2_quorum_produces_a_signature_that_verifies_against_the_group_key() {
+        let y = Scalar::random(&mut OsRng); // the group secret, never reconstructed
+        let b = Scalar::random(&mut OsRng); // degree-1 polynomial f(x) = y + b*x
+        let share_of = |participant: u32| y + b * Scalar::from(participant as u64);
+        let group_verifying_key = &y * &ED25519_BASEPOINT_TABLE;
+
+        let signing_set = [1u32, 2u32];
+        let message = b"admin-authorized SKDM";
+
+        let (nonces_1, commitment_1) = SigningNonces::generate(&mut OsRng);
+        let (nonces_2, commitment_2) = SigningNonces::generate(&mut OsRng);
+        let commitments = vec![(1u32, commitment_1), (2u32, commitment_2)];
+
+        let partials: Vec<_> = [(1u32, nonces_1), (2u32, nonces_2)]
+            .into_iter()
+            .map(|(participant, nonces)| {
+                sign(
+                    participant,
+                    nonces,
+                    share_of(participant),
+                    &signing_set,
+                    &commitments,
+                    &group_verifying_key,
+                    message,
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let signature = aggregate(message, &commitments, &partials).unwrap();
+        assert!(schnorr_verify(&group_verifying_key, message, &signature).unwrap());
+    }
+
+    #[test]
+    fn sign_rejects_a_signing_set_with_a_zero_participant_id() {
+        let (nonces, commitment) = SigningNonces::generate(&mut OsRng);
+        let commitments = vec![(0u32, commitment)];
+        let group_verifying_key = &Scalar::random(&mut OsRng) * &ED25519_BASEPOINT_TABLE;
+
+        let result = sign(
+            0,
+            nonces,
+            Scalar::random(&mut OsRng),
+            &[0u32, 1u32],
+            &commitments,
+            &group_verifying_key,
+            b"message",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn sign_rejects_a_signing_set_with_a_duplicate_participant_id() {
+        let (nonces, commitment) = SigningNonces::generate(&mut OsRng);
+        let commitments = vec![(1u32, commitment)];
+        let group_verifying_key = &Scalar::random(&mut OsRng) * &ED25519_BASEPOINT_TABLE;
+
+        let result = sign(
+            1,
+            nonces,
+            Scalar::random(&mut OsRng),
+            &[1u32, 1u32],
+            &commitments,
+            &group_verifying_key,
+            b"message",
+        );
+        assert!(result.is_err());
+    }
+
+    #[derive(Default)]
+    struct TestSenderKeyStore {
+        record: Option<SenderKeyRecord>,
+    }
+
+    impl SenderKeyStore for TestSenderKeyStore {
+        fn store_sender_key(
+            &mut self,
+            _sender_key_name: &SenderKeyName,
+            record: &SenderKeyRecord,
+        ) -> Result<()> {
+            self.record = Some(record.clone());
+            Ok(())
+        }
+
+        fn load_sender_key(
+            &mut self,
+            _sender_key_name: &SenderKeyName,
+        ) -> Result<Option<SenderKeyRecord>> {
+            Ok(self.record.clone())
+        }
+    }
+
+    // Round-trips a real SKDM through a 2-of-2 FROST quorum signature and
+    // `process_sender_key_distribution_message_with_admin_authorization`,
+    // rather than only checking the FROST math against an arbitrary message.
+    #[test]
+    fn an_admin_quorum_authorized_skdm_is_accepted() {
+        let address = crate::ProtocolAddress::new("+14151111111".to_string(), 1);
+        let sender_key_name = SenderKeyName::new("a-group".to_string(), address).unwrap();
+        let mut sender_store = TestSenderKeyStore::default();
+        let skdm =
+            create_sender_key_distribution_message(&sender_key_name, &mut sender_store, &mut OsRng)
+                .unwrap();
+        let message = skdm.serialized().unwrap();
+
+        let y = Scalar::random(&mut OsRng);
+        let b = Scalar::random(&mut OsRng);
+        let share_of = |participant: u32| y + b * Scalar::from(participant as u64);
+        let group_verifying_key = &y * &ED25519_BASEPOINT_TABLE;
+        let signing_set = [1u32, 2u32];
+
+        let (nonces_1, commitment_1) = SigningNonces::generate(&mut OsRng);
+        let (nonces_2, commitment_2) = SigningNonces::generate(&mut OsRng);
+        let commitments = vec![(1u32, commitment_1), (2u32, commitment_2)];
+
+        let partials: Vec<_> = [(1u32, nonces_1), (2u32, nonces_2)]
+            .into_iter()
+            .map(|(participant, nonces)| {
+                sign(
+                    participant,
+                    nonces,
+                    share_of(participant),
+                    &signing_set,
+                    &commitments,
+                    &group_verifying_key,
+                    &message,
+                )
+                .unwrap()
+            })
+            .collect();
+        let threshold_signature = aggregate(&message, &commitments, &partials).unwrap();
+
+        let mut recipient_store = TestSenderKeyStore::default();
+        assert!(process_sender_key_distribution_message_with_admin_authorization(
+            &sender_key_name,
+            &skdm,
+            &group_verifying_key,
+            &threshold_signature,
+            &mut recipient_store,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn an_skdm_without_a_valid_quorum_signature_is_rejected() {
+        let address = crate::ProtocolAddress::new("+14151111111".to_string(), 1);
+        let sender_key_name = SenderKeyName::new("a-group".to_string(), address).unwrap();
+        let mut sender_store = TestSenderKeyStore::default();
+        let skdm =
+            create_sender_key_distribution_message(&sender_key_name, &mut sender_store, &mut OsRng)
+                .unwrap();
+
+        let group_verifying_key = &Scalar::random(&mut OsRng) * &ED25519_BASEPOINT_TABLE;
+        let bogus_signature = [0u8; 64];
+
+        let mut recipient_store = TestSenderKeyStore::default();
+        assert!(process_sender_key_distribution_message_with_admin_authorization(
+            &sender_key_name,
+            &skdm,
+            &group_verifying_key,
+            &bogus_signature,
+            &mut recipient_store,
+        )
+        .is_err());
+    }
+}
+
+#[cfg(test)]
+mod vrf_tests {
+    use super::vrf::*;
+    use super::*;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn an_honest_proof_verifies_to_the_same_seed_every_time() {
+        let x = Scalar::random(&mut OsRng);
+        let public_key = &x * &ED25519_BASEPOINT_TABLE;
+        let context = context_label(b"group-id", 42);
+
+        let proof = evaluate(&context, &x, &public_key, &mut OsRng).unwrap();
+        let seed_a = verify(&context, &public_key, &proof).unwrap();
+        let seed_b = verify(&context, &public_key, &proof).unwrap();
+
+        assert_eq!(seed_a, seed_b);
+    }
+
+    #[test]
+    fn a_proof_evaluated_under_a_different_context_does_not_verify() {
+        let x = Scalar::random(&mut OsRng);
+        let public_key = &x * &ED25519_BASEPOINT_TABLE;
+
+        let proof = evaluate(&context_label(b"group-a", 42), &x, &public_key, &mut OsRng).unwrap();
+        assert!(verify(&context_label(b"group-b", 42), &public_key, &proof).is_err());
+    }
+
+    #[test]
+    fn a_proof_does_not_verify_under_a_different_public_key() {
+        let x = Scalar::random(&mut OsRng);
+        let public_key = &x * &ED25519_BASEPOINT_TABLE;
+        let other_public_key = &Scalar::random(&mut OsRng) * &ED25519_BASEPOINT_TABLE;
+        let context = context_label(b"group-id", 42);
+
+        let proof = evaluate(&context, &x, &public_key, &mut OsRng).unwrap();
+        assert!(verify(&context, &other_public_key, &proof).is_err());
+    }
+
+    #[derive(Default)]
+    struct TestSenderKeyStore {
+        record: Option<SenderKeyRecord>,
+    }
+
+    impl SenderKeyStore for TestSenderKeyStore {
+        fn store_sender_key(
+            &mut self,
+            _sender_key_name: &SenderKeyName,
+            record: &SenderKeyRecord,
+        ) -> Result<()> {
+            self.record = Some(record.clone());
+            Ok(())
+        }
+
+        fn load_sender_key(
+            &mut self,
+            _sender_key_name: &SenderKeyName,
+        ) -> Result<Option<SenderKeyRecord>> {
+            Ok(self.record.clone())
+        }
+    }
+
+    // `create_sender_key_distribution_message_with_vrf_seed` generates a real
+    // XEdDSA key via `curve::KeyPair::generate` and immediately self-checks
+    // the VRF proof it derives from that key's raw scalar. Every test above
+    // hand-builds a mutually consistent `(x, public_key)` pair directly, which
+    // can never exercise the XEdDSA sign-bit mismatch this regresses: without
+    // negating `signing_scalar` to match `montgomery_public_to_edwards`'s
+    // sign-0 convention, this call fails for roughly half of generated keys.
+    #[test]
+    fn create_with_vrf_seed_succeeds_for_many_freshly_generated_keys() {
+        let address = crate::ProtocolAddress::new("+14151111111".to_string(), 1);
+        let sender_key_name = SenderKeyName::new("a-group".to_string(), address).unwrap();
+
+        for _ in 0..32 {
+            let mut store = TestSenderKeyStore::default();
+            let (skdm, vrf_proof) = create_sender_key_distribution_message_with_vrf_seed(
+                &sender_key_name,
+                b"a-group",
+                &mut store,
+                &mut OsRng,
+            )
+            .unwrap();
+
+            process_sender_key_distribution_message_with_vrf_seed(
+                &sender_key_name,
+                &skdm,
+                b"a-group",
+                &vrf_proof,
+                &mut TestSenderKeyStore::default(),
+            )
+            .unwrap();
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,213 @@
+use crate::curve::{PrivateKey, PublicKey};
+use crate::error::{Result, SignalProtocolError};
+use crate::crypto;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Clone)]
+pub struct SenderMessageKey {
+    iteration: u32,
+    seed: [u8; 32],
+}
+
+impl SenderMessageKey {
+    fn new(iteration: u32, seed: [u8; 32]) -> Result<Self> {
+        Ok(Self { iteration, seed })
+    }
+
+    pub fn iteration(&self) -> Result<u32> {
+        Ok(self.iteration)
+    }
+
+    pub fn cipher_key(&self) -> Result<[u8; 32]> {
+        crypto::hkdf_sha256(&self.seed, b"WhisperGroupCipher")
+    }
+
+    pub fn iv(&self) -> Result<[u8; 16]> {
+        let mut iv = [0u8; 16];
+        iv.copy_from_slice(&crypto::hkdf_sha256(&self.seed, b"WhisperGroupIV")?[..16]);
+        Ok(iv)
+    }
+}
+
+#[derive(Clone)]
+pub struct SenderChainKey {
+    iteration: u32,
+    chain_key: [u8; 32],
+}
+
+impl SenderChainKey {
+    fn new(iteration: u32, chain_key: [u8; 32]) -> Self {
+        Self {
+            iteration,
+            chain_key,
+        }
+    }
+
+    pub fn iteration(&self) -> Result<u32> {
+        Ok(self.iteration)
+    }
+
+    pub fn seed(&self) -> Result<[u8; 32]> {
+        Ok(self.chain_key)
+    }
+
+    pub fn next(&self) -> Result<Self> {
+        Ok(Self::new(
+            self.iteration + 1,
+            crypto::hmac_sha256(&self.chain_key, &[0x02])?,
+        ))
+    }
+
+    pub fn sender_message_key(&self) -> Result<SenderMessageKey> {
+        SenderMessageKey::new(self.iteration, crypto::hmac_sha256(&self.chain_key, &[0x01])?)
+    }
+}
+
+struct SenderKeyStateData {
+    sender_key_id: u32,
+    chain_key: SenderChainKey,
+    signing_key_public: PublicKey,
+    signing_key_private: Option<PrivateKey>,
+    message_keys: HashMap<u32, SenderMessageKey>,
+}
+
+#[derive(Clone)]
+pub struct SenderKeyState {
+    data: Rc<RefCell<SenderKeyStateData>>,
+}
+
+impl SenderKeyState {
+    fn new(
+        sender_key_id: u32,
+        iteration: u32,
+        chain_key: &[u8; 32],
+        signing_key_public: PublicKey,
+        signing_key_private: Option<PrivateKey>,
+    ) -> Self {
+        Self {
+            data: Rc::new(RefCell::new(SenderKeyStateData {
+                sender_key_id,
+                chain_key: SenderChainKey::new(iteration, *chain_key),
+                signing_key_public,
+                signing_key_private,
+                message_keys: HashMap::new(),
+            })),
+        }
+    }
+
+    pub fn sender_key_id(&self) -> Result<u32> {
+        Ok(self.data.borrow().sender_key_id)
+    }
+
+    pub fn sender_chain_key(&self) -> Result<SenderChainKey> {
+        Ok(self.data.borrow().chain_key.clone())
+    }
+
+    pub fn set_sender_chain_key(&self, chain_key: SenderChainKey) -> Result<()> {
+        self.data.borrow_mut().chain_key = chain_key;
+        Ok(())
+    }
+
+    pub fn signing_key_public(&self) -> Result<PublicKey> {
+        Ok(self.data.borrow().signing_key_public.clone())
+    }
+
+    pub fn signing_key_private(&self) -> Result<Option<PrivateKey>> {
+        Ok(self.data.borrow().signing_key_private.clone())
+    }
+
+    pub fn add_sender_message_key(&self, key: &SenderMessageKey) -> Result<()> {
+        self.data
+            .borrow_mut()
+            .message_keys
+            .insert(key.iteration, key.clone());
+        Ok(())
+    }
+
+    pub fn remove_sender_message_key(&self, iteration: u32) -> Result<Option<SenderMessageKey>> {
+        Ok(self.data.borrow_mut().message_keys.remove(&iteration))
+    }
+
+}
+
+#[derive(Clone, Default)]
+pub struct SenderKeyRecord {
+    states: Vec<SenderKeyState>,
+    vrf_proof: Option<[u8; 96]>,
+}
+
+impl SenderKeyRecord {
+    pub fn new_empty() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.states.is_empty())
+    }
+
+    pub fn sender_key_state(&mut self) -> Result<SenderKeyState> {
+        self.states
+            .last()
+            .cloned()
+            .ok_or(SignalProtocolError::InvalidSenderKeyId)
+    }
+
+    pub fn sender_key_state_for_keyid(&mut self, key_id: u32) -> Result<SenderKeyState> {
+        self.states
+            .iter()
+            .rev()
+            .find(|state| state.sender_key_id().ok() == Some(key_id))
+            .cloned()
+            .ok_or(SignalProtocolError::InvalidSenderKeyId)
+    }
+
+    pub fn set_sender_key_state(
+        &mut self,
+        sender_key_id: u32,
+        iteration: u32,
+        chain_key: &[u8; 32],
+        signing_key_public: PublicKey,
+        signing_key_private: Option<PrivateKey>,
+    ) -> Result<()> {
+        self.states.clear();
+        self.states.push(SenderKeyState::new(
+            sender_key_id,
+            iteration,
+            chain_key,
+            signing_key_public,
+            signing_key_private,
+        ));
+        Ok(())
+    }
+
+    pub fn add_sender_key_state(
+        &mut self,
+        sender_key_id: u32,
+        iteration: u32,
+        chain_key: &[u8; 32],
+        signing_key_public: PublicKey,
+        signing_key_private: Option<PrivateKey>,
+    ) -> Result<()> {
+        self.states.push(SenderKeyState::new(
+            sender_key_id,
+            iteration,
+            chain_key,
+            signing_key_public,
+            signing_key_private,
+        ));
+        Ok(())
+    }
+
+    /// The VRF proof for this record's current sender key, if its seed was
+    /// derived with [`crate::group_cipher::create_sender_key_distribution_message_with_vrf_seed`].
+    pub fn vrf_proof(&self) -> Result<Option<[u8; 96]>> {
+        Ok(self.vrf_proof)
+    }
+
+    pub fn set_vrf_proof(&mut self, proof: [u8; 96]) -> Result<()> {
+        self.vrf_proof = Some(proof);
+        Ok(())
+    }
+}